@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::connection::Connection;
+use async_graphql::*;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::time::Duration;
+
+use crate::types::balance::Balance;
+use crate::types::base64::Base64;
+use crate::types::object::{Object, ObjectFilter};
+use crate::types::protocol_config::ProtocolConfigs;
+use crate::types::sui_address::SuiAddress;
+use crate::types::transaction_block::TransactionBlock;
+
+use super::sui_sdk_data_provider::{ExecuteOptions, GasPriceHistory, TransactionFilter};
+
+/// Abstracts the data source backing the GraphQL server's resolvers, so a
+/// resolver can be written once against this trait and run over a live
+/// `SuiClient`, or over compositions of it such as `FallbackDataProvider`
+/// and `QuorumDataProvider`.
+#[async_trait]
+pub(crate) trait DataProvider {
+    async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>>;
+
+    async fn fetch_owned_objs(
+        &self,
+        owner: &SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<ObjectFilter>,
+    ) -> Result<Connection<String, Object>>;
+
+    async fn fetch_balance(&self, address: &SuiAddress, type_: Option<String>) -> Result<Balance>;
+
+    async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>>;
+
+    async fn fetch_chain_id(&self) -> Result<String>;
+
+    async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs>;
+
+    /// Gas prices observed over a recent window of checkpoints, analogous to
+    /// Ethereum's `eth_feeHistory`. Lets a client estimate what gas price to
+    /// pay for its next transaction without guessing.
+    async fn fetch_gas_price_history(
+        &self,
+        checkpoint_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<GasPriceHistory>;
+
+    /// Streams new transaction blocks matching `filter` as they're observed.
+    /// `poll_interval` overrides how often a polling-backed implementation
+    /// checks for new checkpoints; `None` uses the implementation's default.
+    fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+        poll_interval: Option<Duration>,
+    ) -> BoxStream<'_, Result<TransactionBlock>>;
+
+    /// Submits a signed transaction and resolves once it's confirmed
+    /// according to `options`, mirroring `ethers-rs`'s `PendingTransaction`.
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: ExecuteOptions,
+    ) -> Result<TransactionBlock>;
+}
@@ -20,11 +20,20 @@ use crate::types::gas::{GasCostSummary, GasEffects, GasInput};
 use async_graphql::connection::{Connection, Edge};
 use async_graphql::*;
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use move_core_types::language_storage::StructTag;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiGasData, SuiObjectDataOptions, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiRawData, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
-    SuiTransactionBlockResponseOptions,
+    CheckpointId, ExecuteTransactionRequestType, OwnedObjectRef, SuiGasData, SuiObjectDataFilter,
+    SuiObjectDataOptions, SuiObjectResponseQuery, SuiPastObjectResponse, SuiRawData,
+    SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
 };
 use sui_sdk::{
     types::{
@@ -32,6 +41,8 @@ use sui_sdk::{
         digests::TransactionDigest,
         gas::GasCostSummary as NativeGasCostSummary,
         object::Owner as NativeOwner,
+        signature::GenericSignature,
+        transaction::{Transaction, TransactionData},
     },
     SuiClient,
 };
@@ -71,58 +82,70 @@ impl DataProvider for SuiClient {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
-        _filter: Option<ObjectFilter>,
+        filter: Option<ObjectFilter>,
     ) -> Result<Connection<String, Object>> {
         if before.is_some() && after.is_some() {
-            return Err(Error::CursorNoBeforeAfter.extend());
+            return Err(Error::CursorNoBeforeAfter.extend_with(|_, e| e.set("retryable", false)));
         }
         if first.is_some() && last.is_some() {
-            return Err(Error::CursorNoFirstLast.extend());
+            return Err(Error::CursorNoFirstLast.extend_with(|_, e| e.set("retryable", false)));
         }
-        if before.is_some() || last.is_some() {
-            return Err(Error::CursorNoReversePagination.extend());
+        // Relay pairs `first` with `after` and `last` with `before`; mixing
+        // `first` with `before` (or `last` with `after`) doesn't have a
+        // sensible forward/backward direction to page in, so reject it
+        // rather than silently ignoring one of the two arguments.
+        if first.is_some() && before.is_some() {
+            return Err(Error::Internal("first cannot be used with before".to_string())
+                .extend_with(|_, e| e.set("retryable", false)));
+        }
+        if last.is_some() && after.is_some() {
+            return Err(Error::Internal("last cannot be used with after".to_string())
+                .extend_with(|_, e| e.set("retryable", false)));
         }
 
-        let count = first.map(|q| q as usize);
         let native_owner = NativeSuiAddress::from(owner);
-        let query = SuiObjectResponseQuery::new_with_options(SuiObjectDataOptions::full_content());
+        let object_filter = filter
+            .as_ref()
+            .and_then(|f| f.type_.as_ref())
+            .map(|t| {
+                StructTag::from_str(t)
+                    .map(SuiObjectDataFilter::StructType)
+                    .map_err(|e| {
+                        Error::Internal(format!("invalid object filter type: {e}"))
+                            .extend_with(|_, ext| ext.set("retryable", false))
+                    })
+            })
+            .transpose()?;
+        let query = SuiObjectResponseQuery::new(object_filter, Some(SuiObjectDataOptions::full_content()));
 
-        let cursor = match after {
-            Some(q) => Some(
-                NativeObjectID::from_hex_literal(&q)
-                    .map_err(|w| Error::InvalidCursor(w.to_string()).extend())?,
-            ),
-            None => None,
-        };
-
-        let pg = self
-            .read_api()
-            .get_owned_objects(native_owner, Some(query), cursor, count)
-            .await?;
+        let (edges, has_previous_page, has_next_page) = if last.is_some() || before.is_some() {
+            let before_id = before
+                .map(|q| {
+                    NativeObjectID::from_hex_literal(&q).map_err(|w| {
+                        Error::InvalidCursor(w.to_string())
+                            .extend_with(|_, e| e.set("retryable", false))
+                    })
+                })
+                .transpose()?;
 
-        // TODO: support partial success/ failure responses
-        pg.data.iter().try_for_each(|n| {
-            if n.error.is_some() {
-                return Err(Error::CursorConnectionFetchFailed(
-                    n.error.as_ref().unwrap().to_string(),
-                )
-                .extend());
-            } else if n.data.is_none() {
-                return Err(Error::Internal(
-                    "Expected either data or error fields, received neither".to_string(),
-                )
-                .extend());
-            }
-            Ok(())
-        })?;
-        let mut connection = Connection::new(false, pg.has_next_page);
+            let (edges, has_previous_page) =
+                fetch_owned_objs_backward(self, native_owner, &query, before_id, last, &filter)
+                    .await?;
+            (edges, has_previous_page, before_id.is_some())
+        } else {
+            let count = first.map(|q| q as usize);
+            let cursor = match after {
+                Some(q) => Some(NativeObjectID::from_hex_literal(&q).map_err(|w| {
+                    Error::InvalidCursor(w.to_string()).extend_with(|_, e| e.set("retryable", false))
+                })?),
+                None => None,
+            };
 
-        connection.edges.extend(pg.data.into_iter().map(|n| {
-            let g = n.data.unwrap();
-            let o = convert_obj(&g);
+            fetch_owned_objs_forward(self, native_owner, &query, cursor, count, &filter).await?
+        };
 
-            Edge::new(g.object_id.to_string(), o)
-        }));
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection.edges.extend(edges);
         Ok(connection)
     }
 
@@ -144,25 +167,11 @@ impl DataProvider for SuiClient {
             )
             .await?;
 
-        let tx_data = tx.transaction.as_ref().unwrap();
-        let tx_effects = tx.effects.as_ref().unwrap();
-        let sender = *tx_data.data.sender();
-        let gas_effects =
-            convert_to_gas_effects(self, tx_effects.gas_cost_summary(), tx_effects.gas_object())
-                .await?;
+        if tx.transaction.is_none() || tx.effects.is_none() {
+            return Ok(None);
+        }
 
-        Ok(Some(TransactionBlock {
-            digest: digest.to_string(),
-            effects: Some(TransactionBlockEffects {
-                digest: tx_effects.transaction_digest().to_string(),
-                gas_effects,
-            }),
-            sender: Some(Address {
-                address: SuiAddress::from_array(sender.to_inner()),
-            }),
-            bcs: Some(Base64::from(&tx.raw_transaction)),
-            gas_input: Some(convert_to_gas_input(self, tx_data.data.gas_data()).await?),
-        }))
+        Ok(Some(build_transaction_block(self, digest, tx).await?))
     }
 
     async fn fetch_chain_id(&self) -> Result<String> {
@@ -200,6 +209,1058 @@ impl DataProvider for SuiClient {
             protocol_version: cfg.protocol_version.as_u64(),
         })
     }
+
+    async fn fetch_gas_price_history(
+        &self,
+        checkpoint_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<GasPriceHistory> {
+        if reward_percentiles.iter().any(|p| !(0.0..=100.0).contains(p)) {
+            return Err(Error::Internal(
+                "reward_percentiles must all be between 0 and 100".to_string(),
+            )
+            .extend_with(|_, e| e.set("retryable", false)));
+        }
+
+        let latest = self.read_api().get_latest_checkpoint_sequence_number().await?;
+
+        if checkpoint_count == 0 {
+            return Ok(GasPriceHistory {
+                oldest_checkpoint: latest + 1,
+                reference_gas_prices: Vec::new(),
+                percentile_prices: Vec::new(),
+            });
+        }
+
+        let oldest = latest.saturating_sub(checkpoint_count - 1);
+
+        let mut reference_gas_prices = Vec::new();
+        let mut percentile_prices = Vec::new();
+        let mut epoch_rgp_cache = HashMap::new();
+
+        for seq in oldest..=latest {
+            let checkpoint = self
+                .read_api()
+                .get_checkpoint(CheckpointId::SequenceNumber(seq))
+                .await?;
+
+            let reference_gas_price =
+                epoch_reference_gas_price(self, &mut epoch_rgp_cache, checkpoint.epoch).await?;
+            reference_gas_prices.push(BigInt::from(reference_gas_price));
+
+            let mut prices: Vec<u64> = if checkpoint.transactions.is_empty() {
+                Vec::new()
+            } else {
+                self.read_api()
+                    .multi_get_transactions_with_options(
+                        checkpoint.transactions.clone(),
+                        SuiTransactionBlockResponseOptions::new().with_input(),
+                    )
+                    .await?
+                    .iter()
+                    .filter_map(|tx| tx.transaction.as_ref())
+                    .map(|data| data.data.gas_data().price)
+                    .collect()
+            };
+            prices.sort_unstable();
+
+            percentile_prices.push(if prices.is_empty() {
+                reward_percentiles
+                    .iter()
+                    .map(|_| BigInt::from(reference_gas_price))
+                    .collect()
+            } else {
+                reward_percentiles
+                    .iter()
+                    .map(|p| BigInt::from(percentile_price(&prices, *p)))
+                    .collect()
+            });
+        }
+
+        Ok(GasPriceHistory {
+            oldest_checkpoint: oldest,
+            reference_gas_prices,
+            percentile_prices,
+        })
+    }
+
+    fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+        poll_interval: Option<Duration>,
+    ) -> BoxStream<'_, Result<TransactionBlock>> {
+        self.watch_transactions(filter, poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL))
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: ExecuteOptions,
+    ) -> Result<TransactionBlock> {
+        let tx_data: TransactionData = bcs::from_bytes(&tx_bytes.to_vec())
+            .map_err(|e| Error::Internal(e.to_string()).extend_with(|_, ext| ext.set("retryable", false)))?;
+        let sigs = signatures
+            .into_iter()
+            .map(|s| GenericSignature::from_bytes(&s.to_vec()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| Error::Internal(e.to_string()).extend_with(|_, ext| ext.set("retryable", false)))?;
+        let tx = Transaction::from_generic_sig_data(tx_data, sigs);
+
+        let request_type = if options.wait_for_local_execution {
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        } else {
+            ExecuteTransactionRequestType::WaitForEffectsCert
+        };
+
+        let response = self
+            .quorum_driver_api()
+            .execute_transaction_block(
+                tx,
+                SuiTransactionBlockResponseOptions::new(),
+                Some(request_type),
+            )
+            .await?;
+
+        self.confirm_transaction(&response.digest.to_string(), options)
+            .await
+    }
+}
+
+/// Default interval between polls of the underlying JSON-RPC node when a
+/// subscription is backed by polling rather than a native pub/sub transport.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Selects which transactions a `subscribe_transactions` caller is
+/// interested in.
+#[derive(Clone)]
+pub(crate) enum TransactionFilter {
+    /// Every transaction in every new checkpoint.
+    All,
+    /// Transactions sent by this address.
+    Sender(SuiAddress),
+}
+
+impl TransactionFilter {
+    fn matches(&self, tx: &TransactionBlock) -> bool {
+        match self {
+            TransactionFilter::All => true,
+            TransactionFilter::Sender(addr) => {
+                tx.sender.as_ref().map(|s| &s.address) == Some(addr)
+            }
+        }
+    }
+}
+
+/// GraphQL input counterpart of [`TransactionFilter`]: `sender` unset
+/// matches every transaction, `sender` set narrows to that address's.
+#[derive(Clone, InputObject)]
+pub(crate) struct TransactionFilterInput {
+    sender: Option<SuiAddress>,
+}
+
+impl From<TransactionFilterInput> for TransactionFilter {
+    fn from(input: TransactionFilterInput) -> Self {
+        match input.sender {
+            Some(addr) => TransactionFilter::Sender(addr),
+            None => TransactionFilter::All,
+        }
+    }
+}
+
+/// Subscription root resolver, exposing [`DataProvider::subscribe_transactions`]
+/// to GraphQL clients over a long-lived connection instead of a polling
+/// query. Expects an `Arc<dyn DataProvider + Send + Sync>` registered as
+/// schema data, the same backend `Query` resolves reads against.
+pub(crate) struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Streams transaction blocks matching `filter` as they're observed.
+    /// `poll_interval_ms` overrides how often the underlying node is polled;
+    /// omit it to use the provider's default.
+    async fn transactions<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        filter: TransactionFilterInput,
+        poll_interval_ms: Option<u64>,
+    ) -> Result<BoxStream<'ctx, Result<TransactionBlock>>> {
+        let provider = ctx.data::<Arc<dyn DataProvider + Send + Sync>>()?;
+        Ok(provider.subscribe_transactions(filter.into(), poll_interval_ms.map(Duration::from_millis)))
+    }
+}
+
+/// Registers `provider` as schema data on a `Schema::build(query, mutation,
+/// Subscription)` builder, so
+/// `Subscription::transactions` above can actually reach a `DataProvider`
+/// at resolve time. Generic over the concrete `Query`/`Mutation` root types
+/// so it can be dropped into the crate's schema-building code without this
+/// module needing to name them.
+pub(crate) fn with_transaction_subscriptions<Query, Mutation>(
+    builder: SchemaBuilder<Query, Mutation, Subscription>,
+    provider: Arc<dyn DataProvider + Send + Sync>,
+) -> SchemaBuilder<Query, Mutation, Subscription>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+{
+    builder.data(provider)
+}
+
+/// How many times `watch_transactions` attempts a single transaction's
+/// `fetch_tx` before giving up on it and letting the checkpoint's cursor
+/// advance anyway. Bounds how long one permanently-failing transaction (e.g.
+/// a response this client can never parse) can block the stream from ever
+/// reaching later checkpoints.
+const MAX_TX_FETCH_ATTEMPTS: usize = 5;
+
+impl SuiClient {
+    /// Polls `read_api` for new checkpoints every `interval` and yields each
+    /// new transaction block matching `filter` exactly once, analogous to the
+    /// `FilterWatcher`/`SubscriptionStream` abstractions in `ethers-providers`.
+    ///
+    /// Progress is tracked by the next unprocessed checkpoint sequence number
+    /// and the digests already resolved for the checkpoint currently in
+    /// flight. The cursor only advances past a checkpoint once every one of
+    /// its transactions has resolved successfully, so a transaction that
+    /// fails to fetch is retried on the next poll instead of being dropped or
+    /// repeated. Transactions resolved within a single poll are queued and
+    /// drained in the order they appear in the checkpoint. Each failed fetch
+    /// attempt is yielded as an error item; once a transaction has failed
+    /// [`MAX_TX_FETCH_ATTEMPTS`] times it is treated as resolved, so one bad
+    /// transaction can't wedge the stream on the same checkpoint forever.
+    fn watch_transactions(
+        &self,
+        filter: TransactionFilter,
+        interval: Duration,
+    ) -> BoxStream<'_, Result<TransactionBlock>> {
+        struct State {
+            next_checkpoint: Option<u64>,
+            seen_in_checkpoint: HashSet<String>,
+            fetch_attempts: HashMap<String, usize>,
+        }
+
+        stream::unfold(
+            (
+                State {
+                    next_checkpoint: None,
+                    seen_in_checkpoint: HashSet::new(),
+                    fetch_attempts: HashMap::new(),
+                },
+                VecDeque::<Result<TransactionBlock>>::new(),
+            ),
+            move |(mut state, mut pending)| {
+                let filter = filter.clone();
+                async move {
+                    loop {
+                        if let Some(item) = pending.pop_front() {
+                            return Some((item, (state, pending)));
+                        }
+
+                        tokio::time::sleep(interval).await;
+
+                        let latest = match self.read_api().get_latest_checkpoint_sequence_number().await {
+                            Ok(v) => v,
+                            Err(_) => continue, // transient error: resume from the same cursor
+                        };
+                        // Pin the starting checkpoint on the first poll so a
+                        // failure there is retried rather than skipped.
+                        let next = *state.next_checkpoint.get_or_insert(latest);
+                        if next > latest {
+                            continue;
+                        }
+
+                        let checkpoint = match self
+                            .read_api()
+                            .get_checkpoint(CheckpointId::SequenceNumber(next))
+                            .await
+                        {
+                            Ok(c) => c,
+                            Err(_) => continue, // resume from the same cursor on the next poll
+                        };
+
+                        let mut checkpoint_failed = false;
+                        for digest in &checkpoint.transactions {
+                            let digest = digest.to_string();
+                            if state.seen_in_checkpoint.contains(&digest) {
+                                continue;
+                            }
+                            match DataProvider::fetch_tx(self, &digest).await {
+                                Ok(Some(tx)) if filter.matches(&tx) => {
+                                    state.seen_in_checkpoint.insert(digest.clone());
+                                    state.fetch_attempts.remove(&digest);
+                                    pending.push_back(Ok(tx));
+                                }
+                                Ok(_) => {
+                                    state.seen_in_checkpoint.insert(digest.clone());
+                                    state.fetch_attempts.remove(&digest);
+                                }
+                                Err(e) => {
+                                    let attempts = state.fetch_attempts.entry(digest.clone()).or_insert(0);
+                                    *attempts += 1;
+                                    pending.push_back(Err(e));
+                                    if *attempts >= MAX_TX_FETCH_ATTEMPTS {
+                                        // Give up on this transaction so the
+                                        // checkpoint (and the cursor) can
+                                        // still advance; its error already
+                                        // went out above and won't be
+                                        // retried or repeated.
+                                        state.seen_in_checkpoint.insert(digest.clone());
+                                        state.fetch_attempts.remove(&digest);
+                                    } else {
+                                        // Leave `digest` out of
+                                        // `seen_in_checkpoint` so it's
+                                        // retried on the next poll, and don't
+                                        // advance the cursor past a
+                                        // checkpoint that still has an
+                                        // unresolved transaction.
+                                        checkpoint_failed = true;
+                                    }
+                                }
+                            }
+                        }
+
+                        if !checkpoint_failed {
+                            state.next_checkpoint = Some(next + 1);
+                            state.seen_in_checkpoint.clear();
+                        }
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
+
+    /// Polls the node for `digest` until its effects are present, mirroring
+    /// `ethers-rs`'s `PendingTransaction`, which submits a transaction and
+    /// then polls until its receipt appears. Gives up with
+    /// `Error::Internal` once `options.confirmation_timeout` elapses rather
+    /// than waiting forever for a transaction that will never be finalized.
+    ///
+    /// This polls `read_api` directly rather than going through `fetch_tx`:
+    /// a transaction that is visible before its effects are indexed (or not
+    /// found at all yet, immediately after submission) is tolerated and
+    /// retried here, whereas `fetch_tx` reports the first state as
+    /// `Ok(None)` and the second as an error.
+    async fn confirm_transaction(
+        &self,
+        digest: &str,
+        options: ExecuteOptions,
+    ) -> Result<TransactionBlock> {
+        let tx_digest = TransactionDigest::from_str(digest)?;
+
+        let poll = async {
+            loop {
+                match self
+                    .read_api()
+                    .get_transaction_with_options(
+                        tx_digest,
+                        SuiTransactionBlockResponseOptions::full_content(),
+                    )
+                    .await
+                {
+                    Ok(tx) if tx.transaction.is_some() && tx.effects.is_some() => {
+                        return build_transaction_block(self, digest, tx).await;
+                    }
+                    _ => tokio::time::sleep(options.poll_interval).await,
+                }
+            }
+        };
+
+        match tokio::time::timeout(options.confirmation_timeout, poll).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Internal(format!(
+                "timed out waiting for transaction {digest} to be confirmed"
+            ))
+            .extend()),
+        }
+    }
+}
+
+/// Controls how `execute_transaction_block` waits for a submitted
+/// transaction to be confirmed before giving up.
+#[derive(Clone, Copy)]
+pub(crate) struct ExecuteOptions {
+    /// Wait for the transaction to be executed locally by a fastpath
+    /// validator before returning, mirroring the JSON-RPC
+    /// `WaitForLocalExecution` request type. Defaults to `false`, which
+    /// waits only for the effects certificate.
+    pub wait_for_local_execution: bool,
+    /// How long to poll for the transaction's effects before giving up and
+    /// returning `Error::Internal`. Defaults to 60 seconds.
+    pub confirmation_timeout: Duration,
+    /// How often to poll `read_api` while waiting for the transaction's
+    /// effects to land. Defaults to 200 milliseconds.
+    pub poll_interval: Duration,
+}
+
+impl Default for ExecuteOptions {
+    fn default() -> Self {
+        Self {
+            wait_for_local_execution: false,
+            confirmation_timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Looks up the protocol reference gas price in effect during `epoch`,
+/// caching results so that a run of checkpoints from the same epoch only
+/// pays for one `get_epochs` round-trip.
+async fn epoch_reference_gas_price(
+    client: &SuiClient,
+    cache: &mut HashMap<u64, u64>,
+    epoch: u64,
+) -> Result<u64> {
+    if let Some(rgp) = cache.get(&epoch) {
+        return Ok(*rgp);
+    }
+
+    // `get_epochs`'s cursor is exclusive, so epoch 0 has to be requested with
+    // `cursor: None` rather than `saturating_sub(1)`, which would otherwise
+    // clamp to `0` and fetch epoch 1's reference gas price instead.
+    let cursor = if epoch == 0 {
+        None
+    } else {
+        Some((epoch - 1).to_string())
+    };
+    let page = client.read_api().get_epochs(cursor, Some(1), false).await?;
+    let rgp = page
+        .data
+        .first()
+        .map(|e| e.reference_gas_price)
+        .ok_or_else(|| Error::Internal(format!("epoch {epoch} not found")).extend())?;
+
+    cache.insert(epoch, rgp);
+    Ok(rgp)
+}
+
+/// The gas price paid at `percentile` within a checkpoint's `sorted_prices`
+/// (ascending, non-empty), following the same nearest-rank convention as
+/// Ethereum's `eth_feeHistory` reward percentiles.
+fn percentile_price(sorted_prices: &[u64], percentile: f64) -> u64 {
+    let n = sorted_prices.len();
+    let idx = ((percentile / 100.0 * n as f64).ceil() as usize)
+        .saturating_sub(1)
+        .min(n - 1);
+    sorted_prices[idx]
+}
+
+/// Gas prices observed over a recent window of checkpoints, analogous to
+/// Ethereum's `eth_feeHistory`. Lets a client estimate what gas price to pay
+/// for its next transaction without guessing.
+#[derive(SimpleObject)]
+pub(crate) struct GasPriceHistory {
+    /// Sequence number of the oldest checkpoint covered by this history.
+    pub oldest_checkpoint: u64,
+    /// The protocol's reference gas price for each checkpoint in the window,
+    /// oldest first.
+    pub reference_gas_prices: Vec<BigInt>,
+    /// For each checkpoint in the window (oldest first), the gas price
+    /// observed at each of the requested `reward_percentiles`, in the same
+    /// order the percentiles were requested.
+    pub percentile_prices: Vec<Vec<BigInt>>,
+}
+
+/// Whether `delegate_with_fallback!` should fall through to the next backend
+/// after this error instead of returning it to the caller immediately.
+/// Errors raised while validating the caller's own input (bad cursors,
+/// malformed filters, a transaction that fails to decode) are deterministic —
+/// every backend would reject the same input, so retrying just adds latency
+/// — and are tagged with a `"retryable": false` extension where they're
+/// constructed. Anything else, including RPC errors propagated via `?` with
+/// no such extension, is treated as a transport/timeout failure that a
+/// different backend might not hit.
+fn is_retryable(err: &async_graphql::Error) -> bool {
+    match err.extensions.as_ref().and_then(|ext| ext.get("retryable")) {
+        Some(Value::Boolean(retryable)) => *retryable,
+        _ => true,
+    }
+}
+
+/// A [`DataProvider`] that holds an ordered list of backends and delegates
+/// every call to the first one that succeeds, falling through to the next on
+/// a transport/timeout error. This keeps the GraphQL server up when a single
+/// upstream full node RPC goes down, much like how `ethers-providers` layers
+/// multiple JSON-RPC providers behind one fallback middleware.
+pub(crate) struct FallbackDataProvider<P> {
+    backends: Vec<P>,
+    /// How many times to retry each backend before advancing to the next one.
+    retries_per_backend: usize,
+    /// Index into `backends` of whichever one served the most recently
+    /// completed request. An `AtomicUsize` keeps this out of every
+    /// `DataProvider` method's return type, since attribution is a
+    /// diagnostic the caller can poll afterwards rather than part of the
+    /// trait's contract.
+    last_backend_served: AtomicUsize,
+}
+
+impl<P> FallbackDataProvider<P> {
+    pub(crate) fn new(backends: Vec<P>, retries_per_backend: usize) -> Self {
+        Self {
+            backends,
+            retries_per_backend,
+            last_backend_served: AtomicUsize::new(0),
+        }
+    }
+
+    /// Index into the backend list that served the most recently completed
+    /// request.
+    pub(crate) fn last_backend_served(&self) -> usize {
+        self.last_backend_served.load(Ordering::Relaxed)
+    }
+}
+
+/// Runs `$call` against each backend in turn, retrying
+/// `self.retries_per_backend` times per backend, and returns the first
+/// success, recording which backend served it in `self.last_backend_served`.
+/// A non-retryable error (see [`is_retryable`]) is returned immediately
+/// instead of being retried or falling through, since every backend would
+/// hit the same one. If every backend is exhausted, the last error observed
+/// is returned so the caller can see what actually went wrong.
+macro_rules! delegate_with_fallback {
+    ($self:ident, $backend:ident, $call:expr) => {{
+        let mut last_err = None;
+        for (i, $backend) in $self.backends.iter().enumerate() {
+            for _ in 0..=$self.retries_per_backend {
+                match $call {
+                    Ok(v) => {
+                        $self.last_backend_served.store(i, Ordering::Relaxed);
+                        return Ok(v);
+                    }
+                    Err(e) => {
+                        if !is_retryable(&e) {
+                            return Err(e);
+                        }
+                        last_err = Some(e);
+                    }
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            Error::Internal("FallbackDataProvider has no backends configured".to_string()).extend()
+        }))
+    }};
+}
+
+#[async_trait]
+impl<P: DataProvider + Sync + Send> DataProvider for FallbackDataProvider<P> {
+    async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>> {
+        delegate_with_fallback!(self, backend, backend.fetch_obj(address, version).await)
+    }
+
+    async fn fetch_owned_objs(
+        &self,
+        owner: &SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<ObjectFilter>,
+    ) -> Result<Connection<String, Object>> {
+        delegate_with_fallback!(
+            self,
+            backend,
+            backend
+                .fetch_owned_objs(owner, first, after.clone(), last, before.clone(), filter.clone())
+                .await
+        )
+    }
+
+    async fn fetch_balance(&self, address: &SuiAddress, type_: Option<String>) -> Result<Balance> {
+        delegate_with_fallback!(self, backend, backend.fetch_balance(address, type_.clone()).await)
+    }
+
+    async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>> {
+        delegate_with_fallback!(self, backend, backend.fetch_tx(digest).await)
+    }
+
+    async fn fetch_chain_id(&self) -> Result<String> {
+        delegate_with_fallback!(self, backend, backend.fetch_chain_id().await)
+    }
+
+    async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs> {
+        delegate_with_fallback!(self, backend, backend.fetch_protocol_config(version).await)
+    }
+
+    async fn fetch_gas_price_history(
+        &self,
+        checkpoint_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<GasPriceHistory> {
+        delegate_with_fallback!(
+            self,
+            backend,
+            backend
+                .fetch_gas_price_history(checkpoint_count, reward_percentiles)
+                .await
+        )
+    }
+
+    /// Subscriptions are a long-lived stream rather than a single request, so
+    /// there's no per-call result to fail over on; delegate to the first
+    /// configured backend for the life of the stream.
+    fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+        poll_interval: Option<Duration>,
+    ) -> BoxStream<'_, Result<TransactionBlock>> {
+        match self.backends.first() {
+            Some(backend) => backend.subscribe_transactions(filter, poll_interval),
+            None => stream::empty().boxed(),
+        }
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: ExecuteOptions,
+    ) -> Result<TransactionBlock> {
+        delegate_with_fallback!(
+            self,
+            backend,
+            backend
+                .execute_transaction_block(tx_bytes.clone(), signatures.clone(), options)
+                .await
+        )
+    }
+}
+
+/// A [`DataProvider`] that fans a read out to every backend concurrently and
+/// only returns a value once at least `quorum` of them agree, returning
+/// [`Error::Internal`] if agreement can't be reached. Useful when a single
+/// backend returning a stale or incorrect answer is worse than a slower,
+/// cross-checked one. Calls that don't have a meaningful notion of
+/// cross-backend agreement (pagination, subscriptions, submitting a
+/// transaction) fall back to the first backend that succeeds, the same as
+/// [`FallbackDataProvider`].
+pub(crate) struct QuorumDataProvider<P> {
+    backends: Vec<P>,
+    quorum: usize,
+    /// Index into `backends` of whichever one's response was used to make up
+    /// the most recently reached quorum (or, for calls with no quorum of
+    /// their own, whichever one served the request first). An `AtomicUsize`
+    /// keeps this out of every `DataProvider` method's return type, since
+    /// attribution is a diagnostic the caller can poll afterwards rather than
+    /// part of the trait's contract.
+    last_backend_served: AtomicUsize,
+}
+
+impl<P> QuorumDataProvider<P> {
+    pub(crate) fn new(backends: Vec<P>, quorum: usize) -> Self {
+        Self {
+            backends,
+            quorum,
+            last_backend_served: AtomicUsize::new(0),
+        }
+    }
+
+    /// Index into the backend list whose response was used to satisfy the
+    /// most recently completed request.
+    pub(crate) fn last_backend_served(&self) -> usize {
+        self.last_backend_served.load(Ordering::Relaxed)
+    }
+}
+
+impl<P: DataProvider + Sync + Send> QuorumDataProvider<P> {
+    /// Fetches `obj` from every backend and returns it only if at least
+    /// `self.quorum` backends agree on its digest and version. Also returns
+    /// the index of the backend whose response was used, so the caller can
+    /// attribute which backend served the result.
+    async fn quorum_fetch_obj(
+        &self,
+        address: SuiAddress,
+        version: Option<u64>,
+    ) -> Result<(usize, Option<Object>)> {
+        let responses: Vec<_> = futures::future::join_all(
+            self.backends
+                .iter()
+                .map(|b| b.fetch_obj(address, version)),
+        )
+        .await;
+
+        let keys: Vec<Option<String>> = responses
+            .iter()
+            .map(|r| match r {
+                Ok(Some(o)) => Some(format!("{}:{}", o.digest, o.version)),
+                Ok(None) => Some("none".to_string()),
+                Err(_) => None,
+            })
+            .collect();
+
+        match find_quorum_winner(&keys, self.quorum) {
+            Some(i) => {
+                self.last_backend_served.store(i, Ordering::Relaxed);
+                Ok((i, responses[i].clone()?))
+            }
+            None => Err(Error::Internal(format!(
+                "could not reach quorum of {} out of {} backends for object {address}",
+                self.quorum,
+                self.backends.len()
+            ))
+            .extend()),
+        }
+    }
+
+    /// Fetches `address`'s balance of `type_` from every backend and returns
+    /// it only if at least `self.quorum` of them agree on the exact balance.
+    /// Also returns the index of the backend whose response was used, so the
+    /// caller can attribute which backend served the result.
+    async fn quorum_fetch_balance(
+        &self,
+        address: &SuiAddress,
+        type_: Option<String>,
+    ) -> Result<(usize, Balance)> {
+        let responses: Vec<_> = futures::future::join_all(
+            self.backends
+                .iter()
+                .map(|b| b.fetch_balance(address, type_.clone())),
+        )
+        .await;
+
+        let keys: Vec<Option<String>> = responses
+            .iter()
+            .map(|r| r.as_ref().ok().map(|bal| format!("{}:{}", bal.coin_object_count, bal.total_balance)))
+            .collect();
+
+        match find_quorum_winner(&keys, self.quorum) {
+            Some(i) => {
+                self.last_backend_served.store(i, Ordering::Relaxed);
+                Ok((i, responses[i].clone()?))
+            }
+            None => Err(Error::Internal(format!(
+                "could not reach quorum of {} out of {} backends for balance of {address}",
+                self.quorum,
+                self.backends.len()
+            ))
+            .extend()),
+        }
+    }
+}
+
+/// Finds the first backend index whose tally key is shared by at least
+/// `quorum` backends. `keys[i]` is `None` for a backend that errored and so
+/// can't contribute to (or win) any tally.
+fn find_quorum_winner(keys: &[Option<String>], quorum: usize) -> Option<usize> {
+    let mut tally: Vec<(&str, usize, usize)> = Vec::new();
+    for (i, key) in keys.iter().enumerate() {
+        let Some(key) = key else { continue };
+        match tally.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, count, _)) => *count += 1,
+            None => tally.push((key, 1, i)),
+        }
+    }
+    tally
+        .into_iter()
+        .find(|(_, count, _)| *count >= quorum)
+        .map(|(_, _, i)| i)
+}
+
+/// Runs `$call` against each backend in turn and returns the first success.
+/// Used by [`QuorumDataProvider`] for calls that have no meaningful notion of
+/// cross-backend agreement — only [`QuorumDataProvider::quorum_fetch_obj`]
+/// and [`QuorumDataProvider::quorum_fetch_balance`] actually require quorum.
+/// A non-retryable error (see [`is_retryable`]) is returned immediately
+/// instead of being tried against the remaining backends, the same as
+/// [`delegate_with_fallback`].
+macro_rules! delegate_first_success {
+    ($self:ident, $backend:ident, $call:expr) => {{
+        let mut last_err = None;
+        for (i, $backend) in $self.backends.iter().enumerate() {
+            match $call {
+                Ok(v) => {
+                    $self.last_backend_served.store(i, Ordering::Relaxed);
+                    return Ok(v);
+                }
+                Err(e) => {
+                    if !is_retryable(&e) {
+                        return Err(e);
+                    }
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            Error::Internal("QuorumDataProvider has no backends configured".to_string()).extend()
+        }))
+    }};
+}
+
+#[async_trait]
+impl<P: DataProvider + Sync + Send> DataProvider for QuorumDataProvider<P> {
+    async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>> {
+        Ok(self.quorum_fetch_obj(address, version).await?.1)
+    }
+
+    async fn fetch_owned_objs(
+        &self,
+        owner: &SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<ObjectFilter>,
+    ) -> Result<Connection<String, Object>> {
+        delegate_first_success!(
+            self,
+            backend,
+            backend
+                .fetch_owned_objs(owner, first, after.clone(), last, before.clone(), filter.clone())
+                .await
+        )
+    }
+
+    async fn fetch_balance(&self, address: &SuiAddress, type_: Option<String>) -> Result<Balance> {
+        Ok(self.quorum_fetch_balance(address, type_).await?.1)
+    }
+
+    async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>> {
+        delegate_first_success!(self, backend, backend.fetch_tx(digest).await)
+    }
+
+    async fn fetch_chain_id(&self) -> Result<String> {
+        delegate_first_success!(self, backend, backend.fetch_chain_id().await)
+    }
+
+    async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs> {
+        delegate_first_success!(self, backend, backend.fetch_protocol_config(version).await)
+    }
+
+    async fn fetch_gas_price_history(
+        &self,
+        checkpoint_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<GasPriceHistory> {
+        delegate_first_success!(
+            self,
+            backend,
+            backend
+                .fetch_gas_price_history(checkpoint_count, reward_percentiles)
+                .await
+        )
+    }
+
+    fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+        poll_interval: Option<Duration>,
+    ) -> BoxStream<'_, Result<TransactionBlock>> {
+        match self.backends.first() {
+            Some(backend) => backend.subscribe_transactions(filter, poll_interval),
+            None => stream::empty().boxed(),
+        }
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: ExecuteOptions,
+    ) -> Result<TransactionBlock> {
+        delegate_first_success!(
+            self,
+            backend,
+            backend
+                .execute_transaction_block(tx_bytes.clone(), signatures.clone(), options)
+                .await
+        )
+    }
+}
+
+/// Turns a page of `get_owned_objects` responses into GraphQL edges,
+/// applying `filter` client-side. A per-object error reported by the node
+/// (e.g. the object was pruned between the index scan and the fetch) is
+/// logged and that object is left out of the page, rather than failing the
+/// whole page for every object that *did* resolve successfully.
+fn collect_edges(
+    data: Vec<sui_json_rpc_types::SuiObjectResponse>,
+    filter: &Option<ObjectFilter>,
+) -> Result<Vec<Edge<String, Object>>> {
+    Ok(data
+        .into_iter()
+        .filter_map(|n| match n.data {
+            Some(g) => Some(g),
+            None => {
+                if let Some(e) = n.error {
+                    tracing::warn!("skipping object that failed to fetch in owned-objects page: {e}");
+                }
+                None
+            }
+        })
+        .map(|g| (g.object_id.to_string(), convert_obj(&g)))
+        .filter(|(_, o)| object_matches_filter(o, filter))
+        .map(|(id, o)| Edge::new(id, o))
+        .collect())
+}
+
+/// Default page size used by [`fetch_owned_objs_forward`] when the caller
+/// passes neither `first` nor `last`, so an unbounded query can't force the
+/// server to buffer (and return) an owner's entire object set in one
+/// response.
+const DEFAULT_OWNED_OBJECTS_PAGE_SIZE: usize = 50;
+
+/// Pages forward through the owner's objects, applying `filter` client-side
+/// *before* `count` is enforced, so a `first: N` page always returns up to
+/// `N` matching edges (rather than up to `N` raw objects that are then
+/// filtered down) and `has_next_page` reflects whether another matching edge
+/// actually exists beyond what's returned. When `count` is `None`,
+/// [`DEFAULT_OWNED_OBJECTS_PAGE_SIZE`] is used instead of paging through the
+/// owner's full object set.
+async fn fetch_owned_objs_forward(
+    client: &SuiClient,
+    owner: NativeSuiAddress,
+    query: &SuiObjectResponseQuery,
+    mut cursor: Option<NativeObjectID>,
+    count: Option<usize>,
+    filter: &Option<ObjectFilter>,
+) -> Result<(Vec<Edge<String, Object>>, bool, bool)> {
+    let count = count.unwrap_or(DEFAULT_OWNED_OBJECTS_PAGE_SIZE);
+    let mut matched = Vec::new();
+
+    loop {
+        let pg = client
+            .read_api()
+            .get_owned_objects(owner, Some(query.clone()), cursor, None)
+            .await?;
+
+        matched.extend(collect_edges(pg.data, filter)?);
+
+        if matched.len() > count || !pg.has_next_page {
+            break;
+        }
+        cursor = pg.next_cursor;
+    }
+
+    let has_next_page = truncate_forward_page(&mut matched, count);
+
+    Ok((matched, false, has_next_page))
+}
+
+/// Truncates `matched` down to at most `count` entries, returning whether a
+/// further matching edge existed beyond what's kept (i.e. `has_next_page`).
+fn truncate_forward_page<T>(matched: &mut Vec<T>, count: usize) -> bool {
+    if matched.len() > count {
+        matched.truncate(count);
+        true
+    } else {
+        false
+    }
+}
+
+/// The JSON-RPC `get_owned_objects` endpoint only paginates forward, so
+/// backward pagination (`last`/`before`) is emulated by walking forward from
+/// the start of the owner's objects up to `before_id`, then keeping the
+/// trailing `last` of what was collected along the way — similar to how
+/// `ethers`' `LogQuery` pages a range in both directions while tracking a
+/// cursor and `has_previous_page`/`has_next_page` flags.
+async fn fetch_owned_objs_backward(
+    client: &SuiClient,
+    owner: NativeSuiAddress,
+    query: &SuiObjectResponseQuery,
+    before_id: Option<NativeObjectID>,
+    last: Option<u64>,
+    filter: &Option<ObjectFilter>,
+) -> Result<(Vec<Edge<String, Object>>, bool)> {
+    let mut collected = Vec::new();
+    let mut cursor = None;
+    // `before_id` is considered found as soon as it's seen in a page; a
+    // `before` that was never given has nothing to find and isn't stale.
+    let mut found_before = before_id.is_none();
+
+    loop {
+        let pg = client
+            .read_api()
+            .get_owned_objects(owner, Some(query.clone()), cursor, None)
+            .await?;
+
+        let before_pos = before_id.and_then(|id| pg.data.iter().position(|n| n.data.as_ref().map(|d| d.object_id) == Some(id)));
+        found_before |= before_pos.is_some();
+        let page = match before_pos {
+            Some(pos) => pg.data[..pos].to_vec(),
+            None => pg.data,
+        };
+
+        collected.extend(collect_edges(page, filter)?);
+
+        if before_pos.is_some() || !pg.has_next_page {
+            break;
+        }
+        cursor = pg.next_cursor;
+    }
+
+    // A `before_id` that never turns up (deleted, transferred away, or just
+    // invalid) must not silently degrade into "return everything" — that
+    // would hand back objects the caller never asked for.
+    if !found_before {
+        return Err(Error::InvalidCursor(format!(
+            "before cursor {} not found among owner's objects",
+            before_id.expect("found_before is only false when before_id is Some")
+        ))
+        .extend_with(|_, e| e.set("retryable", false)));
+    }
+
+    Ok(truncate_backward_page(collected, last))
+}
+
+/// Keeps the trailing `last` entries of `collected`, returning whether a
+/// further preceding edge existed beyond what's kept (i.e.
+/// `has_previous_page`). `last: None` keeps everything collected.
+fn truncate_backward_page<T>(mut collected: Vec<T>, last: Option<u64>) -> (Vec<T>, bool) {
+    match last {
+        Some(n) if collected.len() > n as usize => {
+            let split_at = collected.len() - n as usize;
+            (collected.split_off(split_at), true)
+        }
+        _ => (collected, false),
+    }
+}
+
+/// Applies an [`ObjectFilter`] to an already-fetched object. `ObjectFilter`
+/// narrows by [`ObjectKind`] and owner; struct type is pushed down into the
+/// `get_owned_objects` query itself rather than checked here.
+fn object_matches_filter(obj: &Object, filter: &Option<ObjectFilter>) -> bool {
+    let Some(filter) = filter else { return true };
+    if let Some(kind) = &filter.kind {
+        if obj.kind.as_ref() != Some(kind) {
+            return false;
+        }
+    }
+    if let Some(owner) = &filter.owner {
+        if obj.owner.as_ref() != Some(owner) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Builds the GraphQL `TransactionBlock` from a JSON-RPC response that is
+/// already known to carry both `transaction` and `effects` data. Shared by
+/// `fetch_tx` and `confirm_transaction` so the latter doesn't have to go
+/// through `fetch_tx`'s all-or-nothing fetch.
+async fn build_transaction_block(
+    cl: &SuiClient,
+    digest: &str,
+    tx: sui_json_rpc_types::SuiTransactionBlockResponse,
+) -> Result<TransactionBlock> {
+    let tx_data = tx.transaction.as_ref().unwrap();
+    let tx_effects = tx.effects.as_ref().unwrap();
+    let sender = *tx_data.data.sender();
+    let gas_effects =
+        convert_to_gas_effects(cl, tx_effects.gas_cost_summary(), tx_effects.gas_object()).await?;
+
+    Ok(TransactionBlock {
+        digest: digest.to_string(),
+        effects: Some(TransactionBlockEffects {
+            digest: tx_effects.transaction_digest().to_string(),
+            gas_effects,
+        }),
+        sender: Some(Address {
+            address: SuiAddress::from_array(sender.to_inner()),
+        }),
+        bcs: Some(Base64::from(&tx.raw_transaction)),
+        gas_input: Some(convert_to_gas_input(cl, tx_data.data.gas_data()).await?),
+    })
 }
 
 fn convert_obj(s: &sui_json_rpc_types::SuiObjectData) -> Object {
@@ -319,3 +1380,84 @@ impl From<&SuiAddress> for NativeSuiAddress {
         NativeSuiAddress::try_from(a.as_slice()).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_price_picks_nearest_rank() {
+        let prices = vec![10, 20, 30, 40, 50];
+        assert_eq!(percentile_price(&prices, 0.0), 10);
+        assert_eq!(percentile_price(&prices, 50.0), 30);
+        assert_eq!(percentile_price(&prices, 100.0), 50);
+    }
+
+    #[test]
+    fn percentile_price_clamps_low_percentile_to_first_element() {
+        // `ceil(p / 100.0 * n)` is `0` when `p` is `0`, so the
+        // `saturating_sub(1)` must not underflow below index `0`.
+        let prices = vec![42];
+        assert_eq!(percentile_price(&prices, 0.0), 42);
+        assert_eq!(percentile_price(&prices, 1.0), 42);
+    }
+
+    #[test]
+    fn percentile_price_never_indexes_past_the_end() {
+        let prices = vec![1, 2, 3];
+        // Ceiling rounding of a percentile right at a boundary (e.g. 100/3 *
+        // 3 == 100) must still land on a valid index, not `prices.len()`.
+        for p in [33.0, 66.0, 99.0, 100.0] {
+            let price = percentile_price(&prices, p);
+            assert!(prices.contains(&price));
+        }
+    }
+
+    #[test]
+    fn find_quorum_winner_requires_quorum_count() {
+        let keys = vec![Some("a".to_string()), Some("a".to_string()), Some("b".to_string())];
+        assert_eq!(find_quorum_winner(&keys, 2), Some(0));
+        assert_eq!(find_quorum_winner(&keys, 3), None);
+    }
+
+    #[test]
+    fn find_quorum_winner_ignores_errored_backends() {
+        let keys = vec![None, Some("a".to_string()), Some("a".to_string())];
+        assert_eq!(find_quorum_winner(&keys, 2), Some(1));
+        assert_eq!(find_quorum_winner(&keys, 3), None);
+    }
+
+    #[test]
+    fn find_quorum_winner_returns_none_when_all_backends_error() {
+        let keys = vec![None, None];
+        assert_eq!(find_quorum_winner(&keys, 1), None);
+    }
+
+    #[test]
+    fn truncate_forward_page_caps_and_flags_has_next_page() {
+        let mut matched = vec![1, 2, 3, 4, 5];
+        assert!(truncate_forward_page(&mut matched, 3));
+        assert_eq!(matched, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn truncate_forward_page_leaves_short_pages_untouched() {
+        let mut matched = vec![1, 2];
+        assert!(!truncate_forward_page(&mut matched, 3));
+        assert_eq!(matched, vec![1, 2]);
+    }
+
+    #[test]
+    fn truncate_backward_page_keeps_the_trailing_n() {
+        let (kept, has_previous_page) = truncate_backward_page(vec![1, 2, 3, 4, 5], Some(2));
+        assert_eq!(kept, vec![4, 5]);
+        assert!(has_previous_page);
+    }
+
+    #[test]
+    fn truncate_backward_page_keeps_everything_when_last_is_none() {
+        let (kept, has_previous_page) = truncate_backward_page(vec![1, 2, 3], None);
+        assert_eq!(kept, vec![1, 2, 3]);
+        assert!(!has_previous_page);
+    }
+}